@@ -1,21 +1,164 @@
 use nom::{
-    bytes::complete::{tag, take},
-    combinator::{map, verify},
+    branch::alt,
+    bytes::complete::{tag, take, take_while1},
+    character::complete::{crlf, digit1, space1},
+    combinator::{map, map_res, verify},
+    error::{Error, ErrorKind},
     number::complete::{be_u16, be_u8},
-    IResult,
+    Err as NomErr, IResult,
 };
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::convert::TryFrom;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::PathBuf;
+
+// Well-known TLV types (PP2_TYPE_*)
+pub const PP2_TYPE_ALPN: u8 = 0x01;
+pub const PP2_TYPE_AUTHORITY: u8 = 0x02;
+pub const PP2_TYPE_CRC32C: u8 = 0x03;
+pub const PP2_TYPE_NOOP: u8 = 0x04;
+pub const PP2_TYPE_UNIQUE_ID: u8 = 0x05;
+pub const PP2_TYPE_SSL: u8 = 0x20;
+pub const PP2_TYPE_NETNS: u8 = 0x30;
+
+// The 12-byte magic signature every PPv2 header starts with
+const SIGNATURE: [u8; 12] = [
+    0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54, 0x0A,
+];
+
+// The PP2_CMD_* values from the fixed header's low nibble
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Local,
+    Proxy,
+}
+
+impl TryFrom<u8> for Command {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, u8> {
+        match value {
+            0x0 => Ok(Command::Local),
+            0x1 => Ok(Command::Proxy),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<Command> for u8 {
+    fn from(command: Command) -> u8 {
+        match command {
+            Command::Local => 0x0,
+            Command::Proxy => 0x1,
+        }
+    }
+}
+
+// The PP2_TRANS_* values from the fixed header's `protocol` nibble
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportProtocol {
+    Unspec,
+    Stream,
+    Dgram,
+}
+
+impl TryFrom<u8> for TransportProtocol {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, u8> {
+        match value {
+            0x0 => Ok(TransportProtocol::Unspec),
+            0x1 => Ok(TransportProtocol::Stream),
+            0x2 => Ok(TransportProtocol::Dgram),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<TransportProtocol> for u8 {
+    fn from(protocol: TransportProtocol) -> u8 {
+        match protocol {
+            TransportProtocol::Unspec => 0x0,
+            TransportProtocol::Stream => 0x1,
+            TransportProtocol::Dgram => 0x2,
+        }
+    }
+}
+
+// The PP2_FAM_* values from the fixed header's `address_family` nibble
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    Unspec,
+    Inet,
+    Inet6,
+    Unix,
+}
+
+impl TryFrom<u8> for AddressFamily {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, u8> {
+        match value {
+            0x0 => Ok(AddressFamily::Unspec),
+            0x1 => Ok(AddressFamily::Inet),
+            0x2 => Ok(AddressFamily::Inet6),
+            0x3 => Ok(AddressFamily::Unix),
+            other => Err(other),
+        }
+    }
+}
+
+impl From<AddressFamily> for u8 {
+    fn from(family: AddressFamily) -> u8 {
+        match family {
+            AddressFamily::Unspec => 0x0,
+            AddressFamily::Inet => 0x1,
+            AddressFamily::Inet6 => 0x2,
+            AddressFamily::Unix => 0x3,
+        }
+    }
+}
 
 // Proxy Protocol v2 header
 #[derive(Debug, PartialEq)]
 pub struct PPv2Header {
     pub version: u8,
-    pub command: u8,
-    pub protocol: u8,
-    pub address_family: u8,
+    pub command: Command,
+    pub protocol: TransportProtocol,
+    pub address_family: AddressFamily,
     pub length: u16,
 }
 
+impl PPv2Header {
+    // Encodes the fixed 16-byte header: signature, version/command byte,
+    // family/protocol byte, and the big-endian length field
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(16);
+        bytes.extend_from_slice(&SIGNATURE);
+        bytes.push((self.version << 4) | (u8::from(self.command) & 0x0F));
+        bytes.push((u8::from(self.address_family) << 4) | (u8::from(self.protocol) & 0x0F));
+        bytes.extend_from_slice(&self.length.to_be_bytes());
+        bytes
+    }
+
+    pub fn is_local(&self) -> bool {
+        self.command == Command::Local
+    }
+}
+
+// The result of `parse`: either a legacy v1 text header or a v2 binary one
+#[derive(Debug, PartialEq)]
+pub enum Header {
+    V1 {
+        protocol: String,
+        source_addr: IpAddr,
+        destination_addr: IpAddr,
+        source_port: u16,
+        destination_port: u16,
+    },
+    V2(PPv2Header),
+}
+
 // IPv4 Address Section
 #[derive(Debug, PartialEq)]
 pub struct IPv4Address {
@@ -25,6 +168,119 @@ pub struct IPv4Address {
     pub destination_port: u16,
 }
 
+impl IPv4Address {
+    // Encodes the source/destination octets followed by the two ports
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(12);
+        bytes.extend_from_slice(&self.source_ip.octets());
+        bytes.extend_from_slice(&self.destination_ip.octets());
+        bytes.extend_from_slice(&self.source_port.to_be_bytes());
+        bytes.extend_from_slice(&self.destination_port.to_be_bytes());
+        bytes
+    }
+
+    pub fn source_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(self.source_ip), self.source_port)
+    }
+
+    pub fn destination_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(self.destination_ip), self.destination_port)
+    }
+}
+
+// A single Type-Length-Value entry from the extension block that may
+// follow the address section
+#[derive(Debug, PartialEq)]
+pub struct Tlv {
+    pub type_: u8,
+    pub value: Vec<u8>,
+}
+
+impl Tlv {
+    // Encodes the type byte, big-endian u16 length, then the value bytes
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(3 + self.value.len());
+        bytes.push(self.type_);
+        bytes.extend_from_slice(&(self.value.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(&self.value);
+        bytes
+    }
+}
+
+// The address section, sized according to `PPv2Header::address_family`
+#[derive(Debug, PartialEq)]
+pub enum Address {
+    Unspec,
+    V4(IPv4Address),
+    V6(IPv6Address),
+    Unix(UnixAddress),
+}
+
+impl Address {
+    // Encodes the address section, dispatching to the variant's own
+    // encoder; `Unspec` carries no address bytes at all
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Address::Unspec => Vec::new(),
+            Address::V4(address) => address.encode(),
+            Address::V6(address) => address.encode(),
+            Address::Unix(address) => address.encode(),
+        }
+    }
+}
+
+// AF_UNIX Address Section: two fixed-width, NUL-padded socket paths
+#[derive(Debug, PartialEq)]
+pub struct UnixAddress {
+    pub source_path: [u8; 108],
+    pub destination_path: [u8; 108],
+}
+
+impl UnixAddress {
+    pub fn source_path(&self) -> PathBuf {
+        trim_nul_path(&self.source_path)
+    }
+
+    pub fn destination_path(&self) -> PathBuf {
+        trim_nul_path(&self.destination_path)
+    }
+
+    // Encodes the two fixed 108-byte NUL-padded path fields
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(216);
+        bytes.extend_from_slice(&self.source_path);
+        bytes.extend_from_slice(&self.destination_path);
+        bytes
+    }
+}
+
+// Trims the trailing NUL padding off a fixed-width Unix socket path field
+fn trim_nul_path(bytes: &[u8; 108]) -> PathBuf {
+    let end = bytes.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+    PathBuf::from(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+// Errors raised while dispatching on `address_family` before the address
+// section is parsed
+#[derive(Debug, PartialEq)]
+pub enum AddressError {
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AddressError::LengthMismatch { expected, actual } => write!(
+                f,
+                "address section needs {} bytes but only {} are available",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AddressError {}
+
 // IPv6 Address Section
 #[derive(Debug, PartialEq)]
 pub struct IPv6Address {
@@ -34,24 +290,46 @@ pub struct IPv6Address {
     pub destination_port: u16,
 }
 
+impl IPv6Address {
+    // Encodes the source/destination octets followed by the two ports
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(36);
+        bytes.extend_from_slice(&self.source_ip.octets());
+        bytes.extend_from_slice(&self.destination_ip.octets());
+        bytes.extend_from_slice(&self.source_port.to_be_bytes());
+        bytes.extend_from_slice(&self.destination_port.to_be_bytes());
+        bytes
+    }
+
+    pub fn source_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V6(self.source_ip), self.source_port)
+    }
+
+    pub fn destination_socket_addr(&self) -> SocketAddr {
+        SocketAddr::new(IpAddr::V6(self.destination_ip), self.destination_port)
+    }
+}
+
 // Parser for the fixed signature
 pub fn parse_signature(input: &[u8]) -> IResult<&[u8], &[u8]> {
-    tag(&[
-        0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54, 0x0A,
-    ])(input)
+    tag(&SIGNATURE[..])(input)
 }
 
 // Parser for the PPv2 header
 pub fn parse_header(input: &[u8]) -> IResult<&[u8], PPv2Header> {
     let (input, _) = parse_signature(input)?;
-    let (input, version_command) = verify(be_u8, |&v| (v >> 4) == 2)(input)?;
-    let (input, proto_family) = be_u8(input)?;
+    let (input, version_command) = verify(be_u8, |&v| {
+        (v >> 4) == 2 && Command::try_from(v & 0x0F).is_ok()
+    })(input)?;
+    let (input, proto_family) = verify(be_u8, |&v| {
+        TransportProtocol::try_from(v & 0x0F).is_ok() && AddressFamily::try_from(v >> 4).is_ok()
+    })(input)?;
     let (input, length) = be_u16(input)?;
 
     let version = version_command >> 4;
-    let command = version_command & 0x0F;
-    let protocol = proto_family & 0x0F;
-    let address_family = proto_family >> 4;
+    let command = Command::try_from(version_command & 0x0F).unwrap();
+    let protocol = TransportProtocol::try_from(proto_family & 0x0F).unwrap();
+    let address_family = AddressFamily::try_from(proto_family >> 4).unwrap();
 
     Ok((
         input,
@@ -127,10 +405,310 @@ pub fn parse_ipv6_address(input: &[u8]) -> IResult<&[u8], IPv6Address> {
     ))
 }
 
+// Parser for a single TLV: one type byte, a big-endian u16 length, then
+// that many value bytes
+pub fn parse_tlv(input: &[u8]) -> IResult<&[u8], Tlv> {
+    let (input, type_) = be_u8(input)?;
+    let (input, len) = be_u16(input)?;
+    let (input, value) = map(take(len as usize), |b: &[u8]| b.to_vec())(input)?;
+
+    Ok((input, Tlv { type_, value }))
+}
+
+// Parses consecutive TLVs out of `input` until `total_len` bytes have been
+// consumed, i.e. the remainder of `header.length` after the address section
+pub fn parse_tlvs(input: &[u8], total_len: usize) -> IResult<&[u8], Vec<Tlv>> {
+    let mut remaining_input = input;
+    let mut consumed = 0usize;
+    let mut tlvs = Vec::new();
+
+    while consumed < total_len {
+        let (next_input, tlv) = parse_tlv(remaining_input)?;
+        consumed += 3 + tlv.value.len();
+        remaining_input = next_input;
+        tlvs.push(tlv);
+    }
+
+    Ok((remaining_input, tlvs))
+}
+
+// Parses a full PPv2 message: the fixed header, the address section, and
+// the trailing TLV vector
+pub fn parse_ppv2(input: &[u8]) -> IResult<&[u8], (PPv2Header, Address, Vec<Tlv>)> {
+    let (input, header) = parse_header(input)?;
+
+    let (address, input) = parse_address_section(&header, input)
+        .map_err(|_| NomErr::Failure(Error::new(input, ErrorKind::Verify)))?;
+
+    let address_len = match address {
+        Address::Unspec => 0usize,
+        Address::V4(_) => 12,
+        Address::V6(_) => 36,
+        Address::Unix(_) => 216,
+    };
+
+    let (input, tlvs) = parse_tlvs(input, header.length as usize - address_len)?;
+
+    Ok((input, (header, address, tlvs)))
+}
+
+// Parser for an AF_UNIX address section
+pub fn parse_unix_address(input: &[u8]) -> IResult<&[u8], UnixAddress> {
+    let (input, source_path) = map(take(108usize), |b: &[u8]| {
+        let mut path = [0u8; 108];
+        path.copy_from_slice(b);
+        path
+    })(input)?;
+    let (input, destination_path) = map(take(108usize), |b: &[u8]| {
+        let mut path = [0u8; 108];
+        path.copy_from_slice(b);
+        path
+    })(input)?;
+
+    Ok((
+        input,
+        UnixAddress {
+            source_path,
+            destination_path,
+        },
+    ))
+}
+
+// Dispatches on `header.address_family`, validating that `header.length`
+// is large enough to hold the expected address section before parsing it,
+// rather than letting a short/malformed length read past the block
+pub fn parse_address_section<'a>(
+    header: &PPv2Header,
+    input: &'a [u8],
+) -> Result<(Address, &'a [u8]), AddressError> {
+    let expected_len = match header.address_family {
+        AddressFamily::Unspec => 0usize,
+        AddressFamily::Inet => 12,
+        AddressFamily::Inet6 => 36,
+        AddressFamily::Unix => 216,
+    };
+
+    if (header.length as usize) < expected_len {
+        return Err(AddressError::LengthMismatch {
+            expected: expected_len,
+            actual: header.length as usize,
+        });
+    }
+
+    let mismatch = || AddressError::LengthMismatch {
+        expected: expected_len,
+        actual: input.len(),
+    };
+
+    match header.address_family {
+        AddressFamily::Unspec => Ok((Address::Unspec, input)),
+        AddressFamily::Inet => parse_ipv4_address(input)
+            .map(|(rest, address)| (Address::V4(address), rest))
+            .map_err(|_| mismatch()),
+        AddressFamily::Inet6 => parse_ipv6_address(input)
+            .map(|(rest, address)| (Address::V6(address), rest))
+            .map_err(|_| mismatch()),
+        AddressFamily::Unix => parse_unix_address(input)
+            .map(|(rest, address)| (Address::Unix(address), rest))
+            .map_err(|_| mismatch()),
+    }
+}
+
+// CRC32C (Castagnoli), reflected polynomial 0x82F63B78
+const CRC32C_POLY: u32 = 0x82F63B78;
+
+const fn crc32c_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut j = 0;
+        while j < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32C_POLY
+            } else {
+                crc >> 1
+            };
+            j += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+const CRC32C_TABLE: [u32; 256] = crc32c_table();
+
+fn crc32c(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+    for &byte in bytes {
+        let index = ((crc ^ byte as u32) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC32C_TABLE[index];
+    }
+    crc ^ 0xFFFFFFFF
+}
+
+// Errors raised while verifying the PP2_TYPE_CRC32C TLV
+#[derive(Debug, PartialEq)]
+pub enum Crc32cError {
+    Mismatch { expected: u32, computed: u32 },
+}
+
+impl fmt::Display for Crc32cError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Crc32cError::Mismatch { expected, computed } => write!(
+                f,
+                "CRC32C mismatch: header claims {:#010x}, computed {:#010x}",
+                expected, computed
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Crc32cError {}
+
+// Verifies the optional PP2_TYPE_CRC32C TLV against the full proxy header
+// bytes (signature, fixed fields, address block, and all TLVs). The TLV's
+// 4 value bytes are zeroed before recomputing the checksum, matching how
+// the sender computed it. `Ok(())` means either the checksum matched or
+// no CRC32C TLV was found to verify, which includes input whose header,
+// address section or TLVs fail to parse; only an actual mismatch is an
+// error.
+pub fn verify_crc32c(full_header_bytes: &[u8]) -> Result<(), Crc32cError> {
+    let (after_header, header) = match parse_header(full_header_bytes) {
+        Ok(ok) => ok,
+        Err(_) => return Ok(()),
+    };
+
+    let (address, after_address) = match parse_address_section(&header, after_header) {
+        Ok(ok) => ok,
+        Err(_) => return Ok(()),
+    };
+
+    let address_len = match address {
+        Address::Unspec => 0usize,
+        Address::V4(_) => 12,
+        Address::V6(_) => 36,
+        Address::Unix(_) => 216,
+    };
+
+    let tlv_total_len = header.length as usize - address_len;
+    let mut tlv_offset = full_header_bytes.len() - after_address.len();
+    let mut consumed = 0usize;
+    let mut remaining = after_address;
+    let mut crc_value_offset = None;
+
+    while consumed < tlv_total_len {
+        let (next_input, tlv) = match parse_tlv(remaining) {
+            Ok(ok) => ok,
+            Err(_) => return Ok(()),
+        };
+
+        if tlv.type_ == PP2_TYPE_CRC32C && tlv.value.len() == 4 {
+            crc_value_offset = Some(tlv_offset + 3);
+        }
+
+        consumed += 3 + tlv.value.len();
+        tlv_offset += 3 + tlv.value.len();
+        remaining = next_input;
+    }
+
+    let crc_value_offset = match crc_value_offset {
+        Some(offset) => offset,
+        None => return Ok(()),
+    };
+
+    let expected = u32::from_be_bytes(
+        full_header_bytes[crc_value_offset..crc_value_offset + 4]
+            .try_into()
+            .unwrap(),
+    );
+
+    let mut buffer = full_header_bytes.to_vec();
+    buffer[crc_value_offset..crc_value_offset + 4].fill(0);
+    let computed = crc32c(&buffer);
+
+    if computed == expected {
+        Ok(())
+    } else {
+        Err(Crc32cError::Mismatch { expected, computed })
+    }
+}
+
+// Encodes a full PPv2 message, back-filling `header.length` from the
+// encoded address section and TLVs rather than trusting the caller to
+// have computed it
+pub fn encode(mut header: PPv2Header, address: &Address, tlvs: &[Tlv]) -> Vec<u8> {
+    let address_bytes = address.encode();
+    let tlv_bytes: Vec<u8> = tlvs.iter().flat_map(Tlv::encode).collect();
+
+    header.length = (address_bytes.len() + tlv_bytes.len()) as u16;
+
+    let mut bytes = header.encode();
+    bytes.extend_from_slice(&address_bytes);
+    bytes.extend_from_slice(&tlv_bytes);
+    bytes
+}
+
+fn parse_v1_token(input: &[u8]) -> IResult<&[u8], &str> {
+    map(take_while1(|c: u8| c != b' ' && c != b'\r'), |b: &[u8]| {
+        std::str::from_utf8(b).unwrap_or_default()
+    })(input)
+}
+
+fn parse_v1_port(input: &[u8]) -> IResult<&[u8], u16> {
+    map_res(digit1, |b: &[u8]| std::str::from_utf8(b).unwrap().parse::<u16>())(input)
+}
+
+// Parser for the PPv1 text header, e.g.
+// "PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n"
+pub fn parse_v1_header(input: &[u8]) -> IResult<&[u8], Header> {
+    let (input, _) = tag("PROXY ")(input)?;
+    let (input, protocol) = parse_v1_token(input)?;
+    let (input, _) = space1(input)?;
+    let (input, source_addr) = parse_v1_token(input)?;
+    let (input, _) = space1(input)?;
+    let (input, destination_addr) = parse_v1_token(input)?;
+    let (input, _) = space1(input)?;
+    let (input, source_port) = parse_v1_port(input)?;
+    let (input, _) = space1(input)?;
+    let (input, destination_port) = parse_v1_port(input)?;
+    let (input, _) = crlf(input)?;
+
+    let source_addr: IpAddr = source_addr
+        .parse()
+        .map_err(|_| NomErr::Failure(Error::new(input, ErrorKind::Verify)))?;
+    let destination_addr: IpAddr = destination_addr
+        .parse()
+        .map_err(|_| NomErr::Failure(Error::new(input, ErrorKind::Verify)))?;
+
+    Ok((
+        input,
+        Header::V1 {
+            protocol: protocol.to_string(),
+            source_addr,
+            destination_addr,
+            source_port,
+            destination_port,
+        },
+    ))
+}
+
+// Unified entry point: tries the v2 binary signature first, then falls
+// back to the v1 text line, so callers don't need to know in advance
+// which generation of header their upstream speaks
+pub fn parse(input: &[u8]) -> IResult<&[u8], Header> {
+    alt((map(parse_header, Header::V2), parse_v1_header))(input)
+}
 
 #[cfg(test)]
 mod test {
-    use crate::{parse_header, PPv2Header};
+    use crate::{
+        encode, parse_address_section, parse_header, parse_ppv2, parse_v1_header, verify_crc32c,
+        Address, AddressError, AddressFamily, Command, Header, IPv4Address, PPv2Header,
+        TransportProtocol, Tlv,
+    };
 
     #[test]
     pub fn test_ppv2_simple() {
@@ -152,11 +730,174 @@ mod test {
             header,
             PPv2Header {
                 version: 2,
-                command: 1,
-                protocol: 1,
-                address_family: 1,
+                command: Command::Proxy,
+                protocol: TransportProtocol::Stream,
+                address_family: AddressFamily::Inet,
                 length: 12
             }
         );
     }
+
+    #[test]
+    pub fn test_ppv2_with_tlvs() {
+        // IPv4 address section followed by a NOOP TLV
+        let example_data = [
+            0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54,
+            0x0A, // Signature
+            0x21, // Version 2, PROXY command
+            0x11, // TCP over IPv4
+            0x00, 0x11, // Length: 12 bytes address + 5 bytes TLV
+            192, 168, 1, 1, // Source IP
+            192, 168, 1, 2, // Destination IP
+            0x1F, 0x90, // Source Port: 8080
+            0x00, 0x50, // Destination Port: 80
+            0x04, 0x00, 0x02, 0xAA, 0xBB, // NOOP TLV, length 2
+        ];
+
+        let (rest, (header, address, tlvs)) = parse_ppv2(&example_data).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(header.length, 17);
+        assert!(matches!(address, Address::V4(_)));
+        assert_eq!(tlvs.len(), 1);
+        assert_eq!(tlvs[0].type_, 0x04);
+        assert_eq!(tlvs[0].value, vec![0xAA, 0xBB]);
+    }
+
+    #[test]
+    pub fn test_parse_v1_header() {
+        let example_data = b"PROXY TCP4 192.168.0.1 192.168.0.11 56324 443\r\n";
+
+        let (_, header) = parse_v1_header(example_data).unwrap();
+        assert_eq!(
+            header,
+            Header::V1 {
+                protocol: "TCP4".to_string(),
+                source_addr: "192.168.0.1".parse().unwrap(),
+                destination_addr: "192.168.0.11".parse().unwrap(),
+                source_port: 56324,
+                destination_port: 443,
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_parse_unix_address() {
+        let mut source_path = [0u8; 108];
+        source_path[..9].copy_from_slice(b"/tmp/src\0");
+        let header = PPv2Header {
+            version: 2,
+            command: Command::Proxy,
+            protocol: TransportProtocol::Unspec,
+            address_family: AddressFamily::Unix,
+            length: 216,
+        };
+        let mut input = Vec::new();
+        input.extend_from_slice(&source_path);
+        input.extend_from_slice(&[0u8; 108]);
+
+        let (address, rest) = parse_address_section(&header, &input).unwrap();
+        assert!(rest.is_empty());
+        match address {
+            Address::Unix(unix) => assert_eq!(unix.source_path().to_str().unwrap(), "/tmp/src"),
+            other => panic!("expected Address::Unix, got {:?}", other),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_address_section_length_mismatch() {
+        let header = PPv2Header {
+            version: 2,
+            command: Command::Proxy,
+            protocol: TransportProtocol::Stream,
+            address_family: AddressFamily::Inet,
+            length: 4,
+        };
+
+        let err = parse_address_section(&header, &[0u8; 4]).unwrap_err();
+        assert_eq!(
+            err,
+            AddressError::LengthMismatch {
+                expected: 12,
+                actual: 4
+            }
+        );
+    }
+
+    #[test]
+    pub fn test_verify_crc32c() {
+        let mut example_data = vec![
+            0x0D, 0x0A, 0x0D, 0x0A, 0x00, 0x0D, 0x0A, 0x51, 0x55, 0x49, 0x54,
+            0x0A, // Signature
+            0x21, // Version 2, PROXY command
+            0x11, // TCP over IPv4
+            0x00, 0x13, // Length: 12 bytes address + 7 bytes TLV
+            192, 168, 1, 1, // Source IP
+            192, 168, 1, 2, // Destination IP
+            0x1F, 0x90, // Source Port: 8080
+            0x00, 0x50, // Destination Port: 80
+            0x03, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, // CRC32C TLV, value zeroed for now
+        ];
+
+        let checksum = crate::crc32c(&example_data);
+        let checksum_offset = example_data.len() - 4;
+        example_data[checksum_offset..].copy_from_slice(&checksum.to_be_bytes());
+
+        assert_eq!(verify_crc32c(&example_data), Ok(()));
+
+        example_data[checksum_offset] ^= 0xFF;
+        assert!(verify_crc32c(&example_data).is_err());
+    }
+
+    #[test]
+    pub fn test_encode_roundtrip() {
+        let header = PPv2Header {
+            version: 2,
+            command: Command::Proxy,
+            protocol: TransportProtocol::Stream,
+            address_family: AddressFamily::Inet,
+            length: 0, // back-filled by `encode`
+        };
+        let address = Address::V4(IPv4Address {
+            source_ip: "192.168.1.1".parse().unwrap(),
+            destination_ip: "192.168.1.2".parse().unwrap(),
+            source_port: 8080,
+            destination_port: 80,
+        });
+        let tlvs = vec![Tlv {
+            type_: 0x04,
+            value: vec![0xAA, 0xBB],
+        }];
+
+        let encoded = encode(header, &address, &tlvs);
+
+        let (rest, (decoded_header, decoded_address, decoded_tlvs)) =
+            parse_ppv2(&encoded).unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(decoded_header.length, 17);
+        assert_eq!(decoded_address, address);
+        assert_eq!(decoded_tlvs, tlvs);
+    }
+
+    #[test]
+    pub fn test_header_is_local_and_socket_addr() {
+        let header = PPv2Header {
+            version: 2,
+            command: Command::Local,
+            protocol: TransportProtocol::Stream,
+            address_family: AddressFamily::Inet,
+            length: 12,
+        };
+        assert!(header.is_local());
+
+        let address = IPv4Address {
+            source_ip: "192.168.1.1".parse().unwrap(),
+            destination_ip: "192.168.1.2".parse().unwrap(),
+            source_port: 8080,
+            destination_port: 80,
+        };
+        assert_eq!(
+            address.source_socket_addr(),
+            "192.168.1.1:8080".parse().unwrap()
+        );
+    }
 }